@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use crate::tree::{letter::HuffLetterAsBytes, HuffTree};
+
+/// Above this code length a single flat table would need an impractically
+/// large `2^max_len` allocation, so a two-level table is built instead: a
+/// root table indexed by the first [`TWO_LEVEL_THRESHOLD`][CompiledDecodeTable::TWO_LEVEL_THRESHOLD]
+/// bits, whose entries either resolve a symbol directly or point at a
+/// sub-table for the remaining bits.
+#[derive(Debug, Clone)]
+enum Slot<L> {
+    Empty,
+    Leaf(L, u8),
+    SubTable(usize),
+}
+
+/// A lookup-table decoder compiled from a [`HuffTree`], decoding multiple
+/// bits per step via a direct table index instead of walking the tree one
+/// bit at a time.
+#[derive(Debug, Clone)]
+pub struct CompiledDecodeTable<L> {
+    max_len: u8,
+    root_bits: u8,
+    root: Vec<Slot<L>>,
+    sub_tables: Vec<Vec<Option<(L, u8)>>>,
+}
+
+impl<L: HuffLetterAsBytes> CompiledDecodeTable<L> {
+    /// Root-table width cap above which a second-level sub-table is used
+    /// instead of growing the root table further.
+    pub const TWO_LEVEL_THRESHOLD: u8 = 12;
+
+    /// Compiles `tree`'s codes into a lookup table.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    /// use huff_coding::comp::CompiledDecodeTable;
+    ///
+    /// let tree = HuffTree::from_weights(ByteWeights::from_bytes(b"abbccc"));
+    /// let table = CompiledDecodeTable::compile(&tree);
+    /// ```
+    pub fn compile(tree: &HuffTree<L>) -> Self {
+        let codes = tree.read_codes();
+        let max_len = codes
+            .values()
+            .map(|code| code.len() as u8)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let root_bits = max_len.min(Self::TWO_LEVEL_THRESHOLD);
+        let sub_width = max_len - root_bits;
+
+        let mut root = (0..1usize << root_bits).map(|_| Slot::Empty).collect::<Vec<_>>();
+        let mut sub_tables: Vec<Vec<Option<(L, u8)>>> = Vec::new();
+
+        for (letter, code) in codes {
+            let len = code.len() as u8;
+            let value = code.value();
+
+            if len <= root_bits {
+                let shift = root_bits - len;
+                let start = (value as usize) << shift;
+                let end = start + (1usize << shift);
+                for slot in &mut root[start..end] {
+                    *slot = Slot::Leaf(letter.clone(), len);
+                }
+            } else {
+                let prefix = (value >> (len - root_bits)) as usize;
+                let sub_index = match root[prefix] {
+                    Slot::SubTable(idx) => idx,
+                    _ => {
+                        let idx = sub_tables.len();
+                        sub_tables.push(vec![None; 1usize << sub_width]);
+                        root[prefix] = Slot::SubTable(idx);
+                        idx
+                    }
+                };
+
+                let remaining_len = len - root_bits;
+                let remaining_value = value & ((1u64 << remaining_len) - 1);
+                let shift = sub_width - remaining_len;
+                let start = (remaining_value as usize) << shift;
+                let end = start + (1usize << shift);
+                for slot in &mut sub_tables[sub_index][start..end] {
+                    *slot = Some((letter.clone(), len));
+                }
+            }
+        }
+
+        CompiledDecodeTable {
+            max_len,
+            root_bits,
+            root,
+            sub_tables,
+        }
+    }
+
+    /// Longest code length this table was compiled for — the number of bits
+    /// [`decode_one`][Self::decode_one] peeks per call.
+    pub fn max_len(&self) -> u8 {
+        self.max_len
+    }
+
+    /// Decodes the next symbol off of `cursor`, peeking [`max_len`][Self::max_len]
+    /// bits (zero-padded past the end of the input) and resolving it with one
+    /// or two table lookups, then advancing `cursor` by the symbol's actual
+    /// code length.
+    ///
+    /// Returns `None` if the peeked bits don't resolve to a known code, which
+    /// only happens for codes that don't cover every bit pattern (e.g. a
+    /// single-symbol alphabet). Past the end of the input the zero padding
+    /// is decoded like any other bits.
+    ///
+    /// # Example
+    /// A Fibonacci weight distribution forces a maximally skewed tree, whose
+    /// codes run well past [`TWO_LEVEL_THRESHOLD`][Self::TWO_LEVEL_THRESHOLD]
+    /// bits and so exercise the two-level root/sub-table path. Decoding
+    /// through the table agrees with walking the tree directly via
+    /// [`HuffTree::decode_next`] symbol-for-symbol:
+    /// ```
+    /// use huff_coding::prelude::*;
+    /// use huff_coding::comp::{BitCursor, CompiledDecodeTable};
+    /// use std::collections::HashMap;
+    ///
+    /// let letters: Vec<u8> = (0u8..16u8).collect();
+    /// let mut weights: HashMap<u8, usize> = HashMap::new();
+    /// let mut fib = (1usize, 1usize);
+    /// for &letter in &letters {
+    ///     weights.insert(letter, fib.0);
+    ///     fib = (fib.1, fib.0 + fib.1);
+    /// }
+    ///
+    /// let tree = HuffTree::from_weights(weights);
+    /// let table = CompiledDecodeTable::compile(&tree);
+    /// assert!(table.max_len() > CompiledDecodeTable::<u8>::TWO_LEVEL_THRESHOLD);
+    ///
+    /// let codes = tree.read_codes();
+    /// let message: Vec<u8> = letters.iter().copied().chain(letters.iter().copied().rev()).collect();
+    /// let mut bits = Vec::new();
+    /// for letter in &message {
+    ///     bits.extend(codes[letter].iter());
+    /// }
+    ///
+    /// let mut table_cursor = BitCursor::new(bits.iter().copied());
+    /// let mut tree_cursor = BitCursor::new(bits.iter().copied());
+    /// for &expected in &message {
+    ///     let (table_letter, table_len) = table.decode_one(&mut table_cursor).unwrap();
+    ///     let (tree_letter, tree_len) = tree.decode_next(&mut tree_cursor).unwrap();
+    ///     assert_eq!(table_letter, expected);
+    ///     assert_eq!(tree_letter, expected);
+    ///     assert_eq!(table_len, tree_len);
+    /// }
+    /// ```
+    pub fn decode_one<I: Iterator<Item = bool>>(&self, cursor: &mut BitCursor<I>) -> Option<(L, u8)> {
+        let peeked = cursor.peek(self.max_len as usize);
+        let sub_width = self.max_len - self.root_bits;
+        let root_index = (peeked >> sub_width) as usize;
+
+        let (letter, len) = match &self.root[root_index] {
+            Slot::Leaf(letter, len) => (letter.clone(), *len),
+            Slot::SubTable(idx) => {
+                let sub_index = (peeked & ((1u64 << sub_width) - 1)) as usize;
+                self.sub_tables[*idx][sub_index].clone()?
+            }
+            Slot::Empty => return None,
+        };
+
+        cursor.advance(len as usize);
+        Some((letter, len))
+    }
+}
+
+/// A cursor over a bit iterator that supports peeking a fixed number of bits
+/// ahead — zero-padded once the iterator is exhausted — without consuming
+/// them, then advancing by however many of those bits were actually used.
+///
+/// This is what lets [`CompiledDecodeTable::decode_one`] look multiple bits
+/// ahead without knowing in advance how many of them a symbol's code uses.
+pub struct BitCursor<I: Iterator<Item = bool>> {
+    bits: I,
+    buffer: VecDeque<bool>,
+}
+
+impl<I: Iterator<Item = bool>> BitCursor<I> {
+    pub fn new(bits: I) -> Self {
+        BitCursor {
+            bits,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    fn fill(&mut self, n: usize) {
+        while self.buffer.len() < n {
+            self.buffer.push_back(self.bits.next().unwrap_or(false));
+        }
+    }
+
+    /// Peeks `n` bits ahead without consuming them, zero-padding past the
+    /// end of the underlying iterator.
+    pub fn peek(&mut self, n: usize) -> u64 {
+        self.fill(n);
+        let mut value = 0u64;
+        for &bit in self.buffer.iter().take(n) {
+            value = (value << 1) | bit as u64;
+        }
+
+        value
+    }
+
+    /// Drops `n` bits off the front of the cursor.
+    pub fn advance(&mut self, n: usize) {
+        for _ in 0..n {
+            self.buffer.pop_front();
+        }
+    }
+}
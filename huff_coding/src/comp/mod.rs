@@ -0,0 +1,91 @@
+use std::convert::TryInto;
+
+use bitvec::prelude::*;
+
+use crate::{
+    tree::{letter::HuffLetter, letter::HuffLetterAsBytes, HuffTree},
+    weights::ByteWeights,
+};
+
+mod decode_table;
+pub use decode_table::{BitCursor, CompiledDecodeTable};
+
+/// Something that can resolve the next symbol off of a [`BitCursor`] —
+/// implemented by both [`HuffTree`] (a bit-by-bit tree walk) and
+/// [`CompiledDecodeTable`] (a table lookup) — so a consumer such as
+/// [`HuffReader`][crate::io::HuffReader] can be handed either.
+pub trait HuffDecoder<L> {
+    /// Decodes the next symbol, returning it along with its code length so
+    /// the caller can advance past it, or `None` if the peeked bits don't
+    /// resolve to one.
+    fn decode_next<I: Iterator<Item = bool>>(&self, cursor: &mut BitCursor<I>) -> Option<(L, u8)>;
+}
+
+impl<L: HuffLetter> HuffDecoder<L> for HuffTree<L> {
+    fn decode_next<I: Iterator<Item = bool>>(&self, cursor: &mut BitCursor<I>) -> Option<(L, u8)> {
+        HuffTree::decode_next(self, cursor)
+    }
+}
+
+impl<L: HuffLetterAsBytes> HuffDecoder<L> for CompiledDecodeTable<L> {
+    fn decode_next<I: Iterator<Item = bool>>(&self, cursor: &mut BitCursor<I>) -> Option<(L, u8)> {
+        self.decode_one(cursor)
+    }
+}
+
+/// Compresses `bytes` with a [`HuffTree`] built over their byte frequencies.
+///
+/// Output layout: a `u32` big-endian byte length of the tree's
+/// [`as_bin`][HuffTree::as_bin] header, the header itself, a `u64`
+/// big-endian count of the original number of bytes, then the packed code
+/// bits.
+///
+/// # Example
+/// ```
+/// use huff_coding::prelude::*;
+///
+/// let bytes = b"abbccc";
+///
+/// let comp_data = compress(bytes);
+/// let decomp_bytes = decompress(&comp_data);
+///
+/// assert_eq!(bytes.to_vec(), decomp_bytes);
+/// ```
+pub fn compress(bytes: &[u8]) -> Vec<u8> {
+    let tree = HuffTree::from_weights(ByteWeights::from_bytes(bytes));
+    let codes = tree.read_codes();
+
+    let tree_bytes = tree.as_bin().into_vec();
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&(tree_bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(&tree_bytes);
+    out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+
+    let mut body: BitVec<Msb0, u8> = BitVec::new();
+    for byte in bytes {
+        body.extend_from_bitslice(&codes.get(byte).unwrap().to_bitvec());
+    }
+    out.extend_from_slice(&body.into_vec());
+
+    out
+}
+
+/// Decompresses `bytes` previously produced by [`compress`], walking the
+/// tree one bit per symbol.
+pub fn decompress(bytes: &[u8]) -> Vec<u8> {
+    let tree_len = u32::from_be_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    let tree_bin: BitVec<Msb0, u8> = BitVec::from_vec(bytes[4..4 + tree_len].to_vec());
+    let tree = HuffTree::<u8>::try_from_bin(tree_bin).expect("malformed compressed header");
+
+    let len_start = 4 + tree_len;
+    let original_len =
+        u64::from_be_bytes(bytes[len_start..len_start + 8].try_into().unwrap()) as usize;
+
+    let body: BitVec<Msb0, u8> = BitVec::from_vec(bytes[len_start + 8..].to_vec());
+    let mut bits = body.into_iter();
+
+    (0..original_len)
+        .map(|_| *tree.decode_one(&mut bits).expect("truncated compressed body"))
+        .collect()
+}
@@ -0,0 +1,197 @@
+//! [`std::io::Read`]/[`std::io::Write`] adapters for encoding and decoding
+//! Huffman data incrementally, without materializing the whole input or
+//! output in memory the way [`compress`][crate::comp::compress]/
+//! [`decompress`][crate::comp::decompress] do.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+
+use crate::{
+    comp::{BitCursor, HuffDecoder},
+    tree::{letter::HuffLetterAsBytes, HuffCode, HuffTree},
+};
+
+/// Incrementally encodes symbols into `W` as Huffman bits.
+///
+/// Buffers bits from each symbol's [`HuffCode`] into a partial byte and
+/// flushes full bytes to the inner writer as they fill up. Call
+/// [`finish`][Self::finish] once done to flush the final, zero-padded
+/// partial byte and learn how many of its bits are actually valid.
+///
+/// # Example
+/// Encoding `b"abbccc"` into a `Vec<u8>` and reading it back through a
+/// [`HuffReader`] — the 9 bits this alphabet needs don't fill a whole
+/// number of bytes, so `finish` reports a non-byte-aligned final byte:
+/// ```
+/// use huff_coding::prelude::*;
+///
+/// let bytes = b"abbccc";
+/// let tree = HuffTree::from_weights(ByteWeights::from_bytes(bytes));
+///
+/// let mut encoded = Vec::new();
+/// let mut writer = HuffWriter::new(&mut encoded, &tree);
+/// for byte in bytes {
+///     writer.write_symbol(byte).unwrap();
+/// }
+/// let (_, valid_bits_in_last_byte) = writer.finish().unwrap();
+///
+/// let codes = tree.read_codes();
+/// let total_bits: u64 = bytes.iter().map(|b| codes[b].len() as u64).sum();
+/// assert_eq!(valid_bits_in_last_byte as u64, total_bits % 8);
+/// assert_ne!(valid_bits_in_last_byte, 0);
+///
+/// let reader: HuffReader<_, u8, _> = HuffReader::new(&encoded[..], tree.clone(), total_bits);
+/// let decoded: Vec<u8> = reader.collect();
+/// assert_eq!(decoded, bytes.to_vec());
+/// ```
+pub struct HuffWriter<W: Write, L: HuffLetterAsBytes> {
+    inner: W,
+    codes: HashMap<L, HuffCode>,
+    buffer: VecDeque<bool>,
+}
+
+impl<W: Write, L: HuffLetterAsBytes> HuffWriter<W, L> {
+    /// Wraps `inner`, encoding symbols with codes read off of `tree`.
+    pub fn new(inner: W, tree: &HuffTree<L>) -> Self {
+        HuffWriter {
+            inner,
+            codes: tree.read_codes(),
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Encodes `letter`, flushing any full bytes its code completed.
+    ///
+    /// # Panics
+    /// Panics if `letter` has no code in the tree this writer was built from.
+    pub fn write_symbol(&mut self, letter: &L) -> io::Result<()> {
+        let code = self
+            .codes
+            .get(letter)
+            .expect("letter has no code in this writer's tree");
+        self.buffer.extend(code.iter());
+
+        self.flush_full_bytes()
+    }
+
+    fn flush_full_bytes(&mut self) -> io::Result<()> {
+        while self.buffer.len() >= 8 {
+            let mut byte = 0u8;
+            for _ in 0..8 {
+                byte = (byte << 1) | self.buffer.pop_front().unwrap() as u8;
+            }
+            self.inner.write_all(&[byte])?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the final partial byte (zero-padded), then returns the inner
+    /// writer along with how many of that last byte's bits are valid — what
+    /// a [`HuffReader`] needs to know exactly where the stream ends.
+    pub fn finish(mut self) -> io::Result<(W, u8)> {
+        let valid_bits = self.buffer.len() as u8;
+
+        if valid_bits > 0 {
+            let mut byte = 0u8;
+            for _ in 0..8 {
+                byte = (byte << 1) | self.buffer.pop_front().unwrap_or(false) as u8;
+            }
+            self.inner.write_all(&[byte])?;
+        }
+
+        Ok((self.inner, valid_bits))
+    }
+}
+
+/// Pulls bits, most-significant first, lazily off of an `R: Read`, one byte
+/// at a time.
+struct ByteBits<R: Read> {
+    inner: R,
+    byte: u8,
+    bits_left: u8,
+}
+
+impl<R: Read> Iterator for ByteBits<R> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.bits_left == 0 {
+            let mut buf = [0u8; 1];
+            match self.inner.read(&mut buf) {
+                Ok(1) => {
+                    self.byte = buf[0];
+                    self.bits_left = 8;
+                }
+                _ => return None,
+            }
+        }
+
+        self.bits_left -= 1;
+        Some((self.byte >> self.bits_left) & 1 == 1)
+    }
+}
+
+/// Incrementally decodes symbols read from `R`, without materializing the
+/// whole input in memory.
+///
+/// Walks a decoder — a [`HuffTree`] or a
+/// [`CompiledDecodeTable`][crate::comp::CompiledDecodeTable], both of which
+/// implement [`HuffDecoder`] — yielding one `L` per call to
+/// [`next`][Iterator::next], and stops exactly at `total_bits`, the bit
+/// count recorded by [`HuffWriter::finish`], rather than reading past the
+/// end of the stream.
+///
+/// # Limitations
+/// The underlying byte source is read through `R::read`, but any `Err` it
+/// returns is treated the same as a clean end of stream (matching
+/// [`Iterator`]'s `Item = L`, which has no room for an error variant). A
+/// mid-stream I/O failure on `R` therefore isn't reported — the missing bits
+/// are zero-padded, and decoding keeps yielding symbols decoded from that
+/// padding until `total_bits` are used up (stopping early only if the
+/// padding doesn't resolve to a code). Iteration running to its end thus
+/// doesn't mean `R` delivered all of its bytes.
+pub struct HuffReader<R: Read, L, D: HuffDecoder<L>> {
+    decoder: D,
+    cursor: BitCursor<ByteBits<R>>,
+    bits_remaining: u64,
+    _letter: PhantomData<L>,
+}
+
+impl<R: Read, L, D: HuffDecoder<L>> HuffReader<R, L, D> {
+    /// Wraps `inner`, decoding with `decoder`. `total_bits` is the exact
+    /// number of valid Huffman-coded bits in `inner` — `8 * (whole bytes
+    /// read so far) + valid_bits_in_last_byte`, where the latter is
+    /// [`HuffWriter::finish`]'s return value — so decoding stops before the
+    /// zero-padding of the final byte is mistaken for data.
+    pub fn new(inner: R, decoder: D, total_bits: u64) -> Self {
+        HuffReader {
+            decoder,
+            cursor: BitCursor::new(ByteBits {
+                inner,
+                byte: 0,
+                bits_left: 0,
+            }),
+            bits_remaining: total_bits,
+            _letter: PhantomData,
+        }
+    }
+}
+
+impl<R: Read, L, D: HuffDecoder<L>> Iterator for HuffReader<R, L, D> {
+    type Item = L;
+
+    fn next(&mut self) -> Option<L> {
+        if self.bits_remaining == 0 {
+            return None;
+        }
+
+        let (letter, len) = self.decoder.decode_next(&mut self.cursor)?;
+        self.bits_remaining = self.bits_remaining.saturating_sub(len as u64);
+
+        Some(letter)
+    }
+}
@@ -37,16 +37,16 @@
 //! let char_codes = tree_chars.read_codes();
 //! 
 //! assert_eq!(
-//!     char_codes.get(&'a').unwrap(),
-//!     &bitvec![Msb0, u8; 0]
+//!     char_codes.get(&'a').unwrap().to_bitvec(),
+//!     bitvec![Msb0, u8; 0]
 //! );
 //! assert_eq!(
-//!     char_codes.get(&'b').unwrap(),
-//!     &bitvec![Msb0, u8; 1, 1]
+//!     char_codes.get(&'b').unwrap().to_bitvec(),
+//!     bitvec![Msb0, u8; 1, 1]
 //! );
 //! assert_eq!(
-//!     char_codes.get(&'c').unwrap(),
-//!     &bitvec![Msb0, u8; 1, 0]
+//!     char_codes.get(&'c').unwrap().to_bitvec(),
+//!     bitvec![Msb0, u8; 1, 0]
 //! );
 //! 
 //! // ------ HuffTree in binary ------
@@ -95,6 +95,8 @@ pub mod tree;
 pub mod weights;
 /// Example compression/decompression functions using the [`HuffTree`][crate::tree::HuffTree] struct.
 pub mod comp;
+/// [`std::io::Read`]/[`std::io::Write`] adapters for streaming Huffman coding.
+pub mod io;
 /// `huff_coding` prelude.
 ///
 /// This collects the general public API into a single spot for inclusion, as
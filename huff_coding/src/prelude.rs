@@ -0,0 +1,6 @@
+pub use crate::{
+    comp::{compress, decompress},
+    io::{HuffReader, HuffWriter},
+    tree::{letter::HuffLetter, letter::HuffLetterAsBytes, HuffBranch, HuffCode, HuffLeaf, HuffTree},
+    weights::{build_weights_map, ByteWeights, Weights},
+};
@@ -0,0 +1,121 @@
+use super::{code::HuffCode, leaf::HuffLeaf};
+
+/// A single node of a [`HuffTree`][super::HuffTree].
+///
+/// Rather than owning its children through a `Box<RefCell<HuffBranch>>` (or,
+/// in older code, an `Rc<RefCell<HuffBranch>>`), a `HuffBranch` refers to its
+/// `left`/`right`/`parent` by their index into the tree's single backing
+/// `Vec<HuffBranch<L>>`. This removes the per-node heap allocation and
+/// `RefCell` borrow checks that pointer-chasing construction and traversal
+/// used to pay for.
+#[derive(Debug, Clone)]
+pub struct HuffBranch<L> {
+    leaf: HuffLeaf<L>,
+
+    pos_in_parent: Option<u8>,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
+
+impl<L> HuffBranch<L> {
+    /// Initializes a new, childless `HuffBranch`.
+    ///
+    /// Children are attached afterwards with [`set_children`][Self::set_children]
+    /// once their indices in the tree's arena are known.
+    pub fn new(leaf: HuffLeaf<L>) -> Self {
+        HuffBranch {
+            leaf,
+            pos_in_parent: None,
+            left: None,
+            right: None,
+            parent: None,
+        }
+    }
+
+    /// Returns a reference to the stored [`HuffLeaf`].
+    pub fn leaf(&self) -> &HuffLeaf<L> {
+        &self.leaf
+    }
+
+    /// Returns a mutable reference to the stored [`HuffLeaf`].
+    pub fn leaf_mut(&mut self) -> &mut HuffLeaf<L> {
+        &mut self.leaf
+    }
+
+    /// Returns this branch's position (`0` or `1`) among its parent's children.
+    pub fn pos_in_parent(&self) -> Option<u8> {
+        self.pos_in_parent
+    }
+
+    /// Sets this branch's position among its parent's children.
+    pub fn set_pos_in_parent(&mut self, pos_in_parent: u8) {
+        self.pos_in_parent = Some(pos_in_parent);
+    }
+
+    /// Returns the `[left, right]` child indices, if this branch isn't a leaf.
+    pub fn children(&self) -> Option<[usize; 2]> {
+        match (self.left, self.right) {
+            (Some(left), Some(right)) => Some([left, right]),
+            _ => None,
+        }
+    }
+
+    /// Returns this branch's child at `pos` (`0` for left, `1` for right),
+    /// if it's been attached yet.
+    ///
+    /// Unlike [`children`][Self::children], this doesn't require both
+    /// children to be set, which matters while a branch is still being
+    /// incrementally built up one child at a time.
+    pub fn child(&self, pos: u8) -> Option<usize> {
+        if pos == 0 {
+            self.left
+        } else {
+            self.right
+        }
+    }
+
+    /// Attaches `left`/`right` as this branch's children, by their index in
+    /// the tree's arena.
+    pub fn set_children(&mut self, left: usize, right: usize) {
+        self.set_child(0, left);
+        self.set_child(1, right);
+    }
+
+    /// Attaches a single child at position `pos` (`0` for left, `1` for
+    /// right), by its index in the tree's arena.
+    pub fn set_child(&mut self, pos: u8, index: usize) {
+        if pos == 0 {
+            self.left = Some(index);
+        } else {
+            self.right = Some(index);
+        }
+    }
+
+    /// Returns this branch's parent's index, if it has one.
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Sets this branch's parent index.
+    pub fn set_parent(&mut self, parent: usize) {
+        self.parent = Some(parent);
+    }
+
+    /// Sets this branch's [`HuffCode`], derived from `parent_code` by
+    /// appending a single bit determined by [`pos_in_parent`][Self::pos_in_parent].
+    ///
+    /// Does nothing if this branch has no `pos_in_parent` set, i.e. it's the
+    /// root.
+    pub fn set_code(&mut self, parent_code: Option<&HuffCode>) {
+        if let Some(pos_in_parent) = self.pos_in_parent {
+            let mut code = HuffCode::new();
+            if let Some(parent_code) = parent_code {
+                code.extend(parent_code);
+            }
+            code.push(pos_in_parent >= 1);
+
+            self.leaf.set_code(code);
+        }
+    }
+}
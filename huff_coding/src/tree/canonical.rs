@@ -0,0 +1,265 @@
+use bitvec::prelude::*;
+
+use super::{
+    branch::HuffBranch, code::HuffCode, leaf::HuffLeaf, letter::HuffLetterAsBytes, HuffTree,
+    TryFromBinError,
+};
+use crate::utils;
+
+impl<L: HuffLetterAsBytes> HuffTree<L> {
+    /// Reassigns every leaf's [`HuffCode`] to canonical codes.
+    ///
+    /// Symbols are sorted by `(code_length, letter)` ascending; the first
+    /// gets code `0`, and every subsequent one gets
+    /// `(prev_code + 1) << (this_len - prev_len)`. This preserves the prefix
+    /// property (and thus decodability) while letting
+    /// [`as_bin_canonical`][Self::as_bin_canonical] serialize just the
+    /// sorted symbol list and its code lengths, instead of the whole tree
+    /// shape.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    ///
+    /// let mut tree = HuffTree::from_weights(ByteWeights::from_bytes(b"abbccc"));
+    /// tree.to_canonical();
+    ///
+    /// let codes = tree.read_codes();
+    /// // 'c' is the most frequent letter, so it gets the shortest code.
+    /// assert_eq!(codes.get(&b'c').unwrap().len(), 1);
+    /// assert_eq!(codes.get(&b'a').unwrap().len(), 2);
+    /// assert_eq!(codes.get(&b'b').unwrap().len(), 2);
+    /// ```
+    pub fn to_canonical(&mut self) {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return,
+        };
+
+        let mut leaves = Vec::new();
+        self.collect_leaves(root, &mut leaves);
+        self.sort_leaves_canonically(&mut leaves);
+
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        let mut prev: Option<(u64, u8)> = None;
+        for index in leaves {
+            // every leaf already has a code here (`set_codes` gives a
+            // single-symbol alphabet's leaf length 1), so the fallback to
+            // length 1 only covers a leaf without one.
+            let len = self.nodes[index]
+                .leaf()
+                .code()
+                .map(|code| code.len() as u8)
+                .unwrap_or(1);
+
+            let code_value = next_canonical_code(prev, len);
+            let mut branch = HuffBranch::new(self.nodes[index].leaf().clone());
+            branch.leaf_mut().set_code(code_from_value(code_value, len));
+            nodes.push(branch);
+
+            prev = Some((code_value, len));
+        }
+
+        // the old shape no longer matches the reassigned codes, so it's
+        // rebuilt from them for the tree-walking decoders and `as_bin`.
+        self.root = build_shape_from_codes(&mut nodes);
+        self.nodes = nodes;
+    }
+
+    fn collect_leaves(&self, index: usize, leaves: &mut Vec<usize>) {
+        match self.nodes[index].children() {
+            Some([left, right]) => {
+                self.collect_leaves(left, leaves);
+                self.collect_leaves(right, leaves);
+            }
+            None => leaves.push(index),
+        }
+    }
+
+    fn sort_leaves_canonically(&self, leaves: &mut [usize]) {
+        leaves.sort_by_key(|&index| {
+            let leaf = self.nodes[index].leaf();
+            let len = leaf.code().map(|code| code.len()).unwrap_or(0);
+            (len, leaf.letter().unwrap().clone())
+        });
+    }
+
+    /// Returns just the sorted `(letter, code_length)` list needed to
+    /// reconstruct this tree's canonical codes with
+    /// [`try_from_canonical_bin`][Self::try_from_canonical_bin] — no tree
+    /// shape bits required.
+    ///
+    /// Call [`to_canonical`][Self::to_canonical] first if the tree's codes
+    /// aren't already canonical; this doesn't check.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    ///
+    /// let mut tree = HuffTree::from_weights(ByteWeights::from_bytes(b"abbccc"));
+    /// tree.to_canonical();
+    ///
+    /// let bin = tree.as_bin_canonical();
+    /// let tree2 = HuffTree::<u8>::try_from_canonical_bin(bin).unwrap();
+    ///
+    /// assert_eq!(tree2.read_codes(), tree.read_codes());
+    /// ```
+    pub fn as_bin_canonical(&self) -> BitVec<Msb0, u8> {
+        let mut leaves = Vec::new();
+        if let Some(root) = self.root() {
+            self.collect_leaves(root, &mut leaves);
+        }
+        self.sort_leaves_canonically(&mut leaves);
+
+        let mut bin = BitVec::new();
+        utils::push_u32(&mut bin, leaves.len() as u32);
+        for index in leaves {
+            let leaf = self.nodes[index].leaf();
+            for byte in leaf.letter().unwrap().as_bytes() {
+                utils::push_byte(&mut bin, byte);
+            }
+            utils::push_byte(&mut bin, leaf.code().unwrap().len() as u8);
+        }
+
+        bin
+    }
+
+    /// Rebuilds a `HuffTree` with the same canonical codes as the tree
+    /// [`as_bin_canonical`][Self::as_bin_canonical] was called on.
+    pub fn try_from_canonical_bin(bin: BitVec<Msb0, u8>) -> Result<Self, TryFromBinError> {
+        let mut bits = bin.into_iter();
+
+        let symbol_count = utils::read_u32(&mut bits)
+            .ok_or_else(|| TryFromBinError("unexpected end of input".into()))?
+            as usize;
+
+        let mut entries = Vec::with_capacity(symbol_count);
+        for _ in 0..symbol_count {
+            let mut letter_bytes = Vec::with_capacity(L::BYTE_LEN);
+            for _ in 0..L::BYTE_LEN {
+                letter_bytes.push(
+                    utils::read_byte(&mut bits)
+                        .ok_or_else(|| TryFromBinError("unexpected end of input".into()))?,
+                );
+            }
+            let len = utils::read_byte(&mut bits)
+                .ok_or_else(|| TryFromBinError("unexpected end of input".into()))?;
+
+            entries.push((L::from_bytes(&letter_bytes), len));
+        }
+        entries.sort_by(|(letter_a, len_a), (letter_b, len_b)| {
+            (len_a, letter_a).cmp(&(len_b, letter_b))
+        });
+
+        let mut nodes = Vec::with_capacity(entries.len());
+        let mut prev: Option<(u64, u8)> = None;
+        for (letter, len) in entries {
+            if len == 0 {
+                return Err(TryFromBinError("zero-length code".into()));
+            }
+            if len > 64 {
+                return Err(TryFromBinError("code length exceeds 64 bits".into()));
+            }
+
+            let code_value = checked_next_canonical_code(prev, len)?;
+
+            let mut branch = HuffBranch::new(HuffLeaf::new(Some(letter), 1));
+            branch.leaf_mut().set_code(code_from_value(code_value, len));
+            nodes.push(branch);
+
+            prev = Some((code_value, len));
+        }
+
+        let root = build_shape_from_codes(&mut nodes);
+
+        Ok(HuffTree { nodes, root })
+    }
+}
+
+fn next_canonical_code(prev: Option<(u64, u8)>, len: u8) -> u64 {
+    match prev {
+        None => 0,
+        Some((prev_code, prev_len)) => (prev_code + 1) << (len - prev_len),
+    }
+}
+
+/// Like [`next_canonical_code`], but for lengths parsed from untrusted input:
+/// computes the next code in `u128` (wide enough that the shift can't
+/// overflow even for an adversarial `len` up to 64) and checks the result
+/// against Kraft's inequality — that it still fits in `len` bits — instead of
+/// trusting the caller's lengths form a valid prefix code.
+///
+/// Entries are assumed sorted by `(len, letter)` ascending, so `len >=
+/// prev_len` always holds here.
+fn checked_next_canonical_code(
+    prev: Option<(u64, u8)>,
+    len: u8,
+) -> Result<u64, TryFromBinError> {
+    let code_value = match prev {
+        None => 0u128,
+        Some((prev_code, prev_len)) => (prev_code as u128 + 1) << (len - prev_len),
+    };
+
+    if code_value >= 1u128 << len {
+        return Err(TryFromBinError(
+            "code lengths don't form a valid canonical code (Kraft's inequality violated)".into(),
+        ));
+    }
+
+    Ok(code_value as u64)
+}
+
+fn code_from_value(value: u64, len: u8) -> HuffCode {
+    let mut code = HuffCode::new();
+    for i in (0..len).rev() {
+        code.push((value >> i) & 1 == 1);
+    }
+    code
+}
+
+/// Rebuilds a prefix tree's shape purely from each already-pushed leaf's
+/// resolved [`HuffCode`] — needed because canonical decoding skips storing
+/// the tree shape, but [`HuffTree::decode_one`] still walks one.
+fn build_shape_from_codes<L>(nodes: &mut Vec<HuffBranch<L>>) -> Option<usize> {
+    if nodes.is_empty() {
+        return None;
+    }
+    if nodes.len() == 1 {
+        return Some(0);
+    }
+
+    let root_index = nodes.len();
+    nodes.push(HuffBranch::new(HuffLeaf::new(None, 0)));
+
+    for leaf_index in 0..root_index {
+        let code = nodes[leaf_index].leaf().code().cloned().unwrap();
+
+        let mut current = root_index;
+        let last_bit = code.len() - 1;
+        for (depth, bit) in code.into_iter().enumerate() {
+            let pos = bit as u8;
+
+            if depth == last_bit {
+                attach_child(nodes, current, pos, leaf_index);
+            } else {
+                current = match nodes[current].child(pos) {
+                    Some(existing) => existing,
+                    None => {
+                        let joint_index = nodes.len();
+                        nodes.push(HuffBranch::new(HuffLeaf::new(None, 0)));
+                        attach_child(nodes, current, pos, joint_index);
+                        joint_index
+                    }
+                };
+            }
+        }
+    }
+
+    Some(root_index)
+}
+
+fn attach_child<L>(nodes: &mut [HuffBranch<L>], parent: usize, pos: u8, child: usize) {
+    nodes[parent].set_child(pos, child);
+    nodes[child].set_pos_in_parent(pos);
+    nodes[child].set_parent(parent);
+}
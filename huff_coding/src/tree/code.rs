@@ -0,0 +1,148 @@
+use std::iter::FromIterator;
+
+use bitvec::prelude::*;
+
+/// A Huffman prefix code: the bit value stored right-aligned in a `u64`,
+/// alongside its length.
+///
+/// Used for a single symbol's code, as returned by [`HuffTree::read_codes`][super::HuffTree::read_codes]
+/// and built up by [`HuffBranch::set_code`][super::HuffBranch::set_code].
+/// Huffman codes over realistic alphabets virtually never exceed 64 bits, so
+/// this packed representation avoids the per-symbol allocation a growable
+/// bit container like [`bitvec`]'s `BitVec` (what this used to be a plain
+/// alias for) pays for, and makes comparing codes or filling a
+/// [`CompiledDecodeTable`][crate::comp::CompiledDecodeTable] a plain integer
+/// operation instead of per-bit work.
+///
+/// For the tree's own serialized binary representations — which can run
+/// well past 64 bits — `HuffTree` still builds up a `BitVec` directly; only
+/// individual codes are packed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HuffCode {
+    bits: u64,
+    len: u8,
+}
+
+impl HuffCode {
+    /// Returns an empty code.
+    pub fn new() -> Self {
+        HuffCode::default()
+    }
+
+    /// Number of bits in this code.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if this code has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The code's bits, right-aligned in a `u64`.
+    pub fn value(&self) -> u64 {
+        self.bits
+    }
+
+    /// Appends a single bit.
+    ///
+    /// # Panics
+    /// Panics if the code would grow past 64 bits.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    /// use huff_coding::bitvec::prelude::*;
+    ///
+    /// let mut code = HuffCode::new();
+    /// code.push(true);
+    /// code.push(false);
+    /// code.push(true);
+    ///
+    /// assert_eq!(code.to_bitvec(), bitvec![Msb0, u8; 1, 0, 1]);
+    /// assert_eq!(HuffCode::from_bitslice(&code.to_bitvec()), code);
+    /// ```
+    pub fn push(&mut self, bit: bool) {
+        assert!(self.len < 64, "HuffCode cannot hold more than 64 bits");
+        self.bits = (self.bits << 1) | bit as u64;
+        self.len += 1;
+    }
+
+    /// Appends every bit of `other`.
+    ///
+    /// # Panics
+    /// Panics if the combined code would grow past 64 bits.
+    pub fn extend(&mut self, other: &HuffCode) {
+        assert!(
+            self.len as u32 + other.len as u32 <= 64,
+            "HuffCode cannot hold more than 64 bits"
+        );
+        // a 64-bit `other` leaves `self` empty, and `<< 64` would overflow.
+        self.bits = self.bits.checked_shl(other.len as u32).unwrap_or(0) | other.bits;
+        self.len += other.len;
+    }
+
+    /// Returns an MSB-first iterator over the code's bits.
+    pub fn iter(&self) -> HuffCodeIter {
+        HuffCodeIter { code: *self, pos: 0 }
+    }
+
+    /// Converts to a [`bitvec`] `BitVec`.
+    pub fn to_bitvec(&self) -> BitVec<Msb0, u8> {
+        self.iter().collect()
+    }
+
+    /// Builds a `HuffCode` from a `bitvec` bit slice.
+    ///
+    /// # Panics
+    /// Panics if `bits` holds more than 64 bits.
+    pub fn from_bitslice(bits: &BitSlice<Msb0, u8>) -> Self {
+        let mut code = HuffCode::new();
+        for bit in bits {
+            code.push(*bit);
+        }
+        code
+    }
+}
+
+/// MSB-first iterator over a [`HuffCode`]'s bits, returned by [`HuffCode::iter`].
+#[derive(Debug, Clone)]
+pub struct HuffCodeIter {
+    code: HuffCode,
+    pos: u8,
+}
+
+impl Iterator for HuffCodeIter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.pos >= self.code.len {
+            return None;
+        }
+
+        let shift = self.code.len - 1 - self.pos;
+        let bit = (self.code.bits >> shift) & 1 == 1;
+        self.pos += 1;
+
+        Some(bit)
+    }
+}
+
+impl IntoIterator for HuffCode {
+    type Item = bool;
+    type IntoIter = HuffCodeIter;
+
+    fn into_iter(self) -> HuffCodeIter {
+        HuffCodeIter { code: self, pos: 0 }
+    }
+}
+
+impl FromIterator<bool> for HuffCode {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut code = HuffCode::new();
+        for bit in iter {
+            code.push(bit);
+        }
+        code
+    }
+}
@@ -0,0 +1,51 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// An index into a [`HuffTree`][super::HuffTree]'s node arena, paired with the
+/// frequency it was pushed with, ordered so that the lowest frequency sorts
+/// first out of the (max-heap) [`BinaryHeap`].
+#[derive(Debug, Eq, PartialEq)]
+struct HeapEntry {
+    frequency: usize,
+    index: usize,
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.frequency.cmp(&self.frequency)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A min-heap over indices into a [`HuffTree`][super::HuffTree]'s node arena,
+/// ordered by ascending [`HuffLeaf`][super::HuffLeaf] frequency.
+///
+/// Operating on indices rather than boxed branches avoids a heap allocation
+/// and pointer chase per comparison during tree construction.
+pub(crate) struct HuffBranchHeap {
+    heap: BinaryHeap<HeapEntry>,
+}
+
+impl HuffBranchHeap {
+    pub(crate) fn new() -> Self {
+        HuffBranchHeap {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, index: usize, frequency: usize) {
+        self.heap.push(HeapEntry { frequency, index });
+    }
+
+    pub(crate) fn pop_min(&mut self) -> Option<usize> {
+        self.heap.pop().map(|entry| entry.index)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
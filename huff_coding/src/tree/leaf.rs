@@ -0,0 +1,42 @@
+use super::code::HuffCode;
+
+/// Data held by a single [`HuffBranch`][super::HuffBranch]: its frequency,
+/// its resolved [`HuffCode`] (once assigned), and — for leaves — the letter
+/// itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HuffLeaf<L> {
+    letter: Option<L>,
+    frequency: usize,
+    code: Option<HuffCode>,
+}
+
+impl<L> HuffLeaf<L> {
+    /// Initializes a new `HuffLeaf`. `letter` is `None` for joint (non-leaf) nodes.
+    pub fn new(letter: Option<L>, frequency: usize) -> Self {
+        HuffLeaf {
+            letter,
+            frequency,
+            code: None,
+        }
+    }
+
+    /// Returns the stored letter, if this leaf represents one.
+    pub fn letter(&self) -> Option<&L> {
+        self.letter.as_ref()
+    }
+
+    /// Returns the stored frequency.
+    pub fn frequency(&self) -> usize {
+        self.frequency
+    }
+
+    /// Returns the assigned [`HuffCode`], if one has been set.
+    pub fn code(&self) -> Option<&HuffCode> {
+        self.code.as_ref()
+    }
+
+    /// Sets the leaf's code.
+    pub fn set_code(&mut self, code: HuffCode) {
+        self.code = Some(code);
+    }
+}
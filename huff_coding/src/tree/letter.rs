@@ -0,0 +1,43 @@
+use std::hash::Hash;
+
+/// Trait bound required of every symbol stored in a [`HuffTree`][super::HuffTree].
+///
+/// Blanket-implemented for any type satisfying the bounds, so every primitive
+/// type (except floats, which aren't `Eq`/`Ord`) implements it out of the box.
+pub trait HuffLetter: Clone + Eq + Hash + Ord {}
+impl<T: Clone + Eq + Hash + Ord> HuffLetter for T {}
+
+/// Extension of [`HuffLetter`] for letters that can be losslessly converted
+/// to and from a fixed-size big-endian byte representation.
+///
+/// Required to serialize a [`HuffTree`][super::HuffTree] to/from its binary
+/// form (see [`HuffTree::as_bin`][super::HuffTree::as_bin]).
+pub trait HuffLetterAsBytes: HuffLetter {
+    /// Number of bytes in [`as_bytes`][HuffLetterAsBytes::as_bytes]'s output.
+    const BYTE_LEN: usize;
+
+    fn as_bytes(&self) -> Vec<u8>;
+    fn from_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_huff_letter_as_bytes {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl HuffLetterAsBytes for $t {
+                const BYTE_LEN: usize = std::mem::size_of::<$t>();
+
+                fn as_bytes(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn from_bytes(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$t>()];
+                    buf.copy_from_slice(bytes);
+                    <$t>::from_be_bytes(buf)
+                }
+            }
+        )+
+    };
+}
+
+impl_huff_letter_as_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
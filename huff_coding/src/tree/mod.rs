@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use bitvec::prelude::*;
+
+pub mod letter;
+
+mod branch;
+mod canonical;
+mod code;
+mod heap;
+mod leaf;
+
+pub use branch::HuffBranch;
+pub use code::HuffCode;
+pub use leaf::HuffLeaf;
+pub use letter::{HuffLetter, HuffLetterAsBytes};
+
+use crate::{utils, weights::Weights};
+use heap::HuffBranchHeap;
+
+/// Struct representing a Huffman tree over an alphabet of `L`.
+///
+/// A `HuffTree` is comprised of [`HuffBranch`]es, each having either 2 or 0
+/// children, with the root being the top one and every leaf holding a letter.
+///
+/// Rather than an `Option<Box<RefCell<HuffBranch>>>` tree of boxed, pointer-chased
+/// nodes, the whole tree lives in a single `Vec<HuffBranch<L>>` sized exactly
+/// `2 * n - 1` for `n` letters (a full Huffman tree always has that many
+/// nodes), with children and parents referenced by their index into that
+/// vector. For a tree grown with [`HuffTree::from_weights`] or read with
+/// [`HuffTree::try_from_bin`] the root is the last-pushed index; trees whose
+/// shape is rebuilt from canonical codes store the root right after the
+/// leaves instead.
+///
+/// Grown with [`HuffTree::from_weights`].
+#[derive(Debug, Clone)]
+pub struct HuffTree<L> {
+    nodes: Vec<HuffBranch<L>>,
+    root: Option<usize>,
+}
+
+impl<L: HuffLetter> HuffTree<L> {
+    /// Builds a `HuffTree` from anything implementing [`Weights`].
+    ///
+    /// # Panics
+    /// Panics if `weights` yields no letters.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    ///
+    /// let weights = build_weights_map(&['a', 'b', 'b', 'c', 'c', 'c']);
+    /// let tree = HuffTree::from_weights(weights);
+    /// ```
+    pub fn from_weights(weights: impl Weights<L>) -> Self {
+        let letters_with_weights = weights.letters_with_weights();
+        assert!(!letters_with_weights.is_empty(), "weights are empty");
+
+        let mut nodes = Vec::with_capacity(2 * letters_with_weights.len() - 1);
+        let mut heap = HuffBranchHeap::new();
+
+        for (letter, frequency) in letters_with_weights {
+            let index = nodes.len();
+            nodes.push(HuffBranch::new(HuffLeaf::new(Some(letter), frequency)));
+            heap.push(index, frequency);
+        }
+
+        while heap.len() > 1 {
+            let min = heap.pop_min().unwrap();
+            let next_min = heap.pop_min().unwrap();
+
+            nodes[min].set_pos_in_parent(0);
+            nodes[next_min].set_pos_in_parent(1);
+
+            let frequency = nodes[min].leaf().frequency() + nodes[next_min].leaf().frequency();
+
+            let parent_index = nodes.len();
+            let mut parent = HuffBranch::new(HuffLeaf::new(None, frequency));
+            parent.set_children(min, next_min);
+            nodes.push(parent);
+
+            nodes[min].set_parent(parent_index);
+            nodes[next_min].set_parent(parent_index);
+
+            heap.push(parent_index, frequency);
+        }
+        let root = heap.pop_min();
+
+        let mut tree = HuffTree { nodes, root };
+        tree.set_codes();
+
+        tree
+    }
+
+    /// Returns the index of the root node, if the tree isn't empty.
+    fn root(&self) -> Option<usize> {
+        self.root
+    }
+
+    fn set_codes(&mut self) {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return,
+        };
+
+        // a single-letter alphabet's root is itself a leaf, and never
+        // receives a code through the recursive parent -> child walk below,
+        // so it's forced to the only sensible one: a single `0` bit.
+        if self.nodes[root].children().is_none() {
+            let mut code = HuffCode::new();
+            code.push(false);
+            self.nodes[root].leaf_mut().set_code(code);
+            return;
+        }
+
+        self.set_branch_codes(root);
+    }
+
+    fn set_branch_codes(&mut self, index: usize) {
+        if let Some([left, right]) = self.nodes[index].children() {
+            let parent_code = self.nodes[index].leaf().code().cloned();
+
+            for child in [left, right] {
+                self.nodes[child].set_code(parent_code.as_ref());
+                self.set_branch_codes(child);
+            }
+        }
+    }
+
+    /// Returns every letter stored in the tree along with its [`HuffCode`].
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    ///
+    /// let tree = HuffTree::from_weights(build_weights_map(&['a', 'b', 'b']));
+    /// let codes = tree.read_codes();
+    ///
+    /// assert!(codes.contains_key(&'a'));
+    /// assert!(codes.contains_key(&'b'));
+    /// ```
+    pub fn read_codes(&self) -> HashMap<L, HuffCode> {
+        let mut codes = HashMap::new();
+        if let Some(root) = self.root() {
+            self.read_codes_from(root, &mut codes);
+        }
+
+        codes
+    }
+
+    fn read_codes_from(&self, index: usize, codes: &mut HashMap<L, HuffCode>) {
+        let branch = &self.nodes[index];
+        match branch.leaf().letter() {
+            Some(letter) => {
+                codes.insert(letter.clone(), branch.leaf().code().cloned().unwrap());
+            }
+            None => {
+                if let Some([left, right]) = branch.children() {
+                    self.read_codes_from(left, codes);
+                    self.read_codes_from(right, codes);
+                }
+            }
+        }
+    }
+
+    /// Decodes a single letter off of `bits`, walking the tree one bit per
+    /// level from the root down to a leaf.
+    ///
+    /// Returns `None` once `bits` runs out before a leaf is reached.
+    pub(crate) fn decode_one(&self, bits: &mut impl Iterator<Item = bool>) -> Option<&L> {
+        let mut index = self.root()?;
+        while let Some([left, right]) = self.nodes[index].children() {
+            index = if bits.next()? { right } else { left };
+        }
+
+        self.nodes[index].leaf().letter()
+    }
+
+    /// Decodes the next symbol off of `cursor`, walking the tree one bit at
+    /// a time.
+    ///
+    /// Unlike [`decode_one`][Self::decode_one], this takes a
+    /// [`BitCursor`][crate::comp::BitCursor] and returns the decoded
+    /// letter's code length along with it, which is what lets
+    /// [`HuffReader`][crate::io::HuffReader] use a `HuffTree` and a
+    /// [`CompiledDecodeTable`][crate::comp::CompiledDecodeTable]
+    /// interchangeably through the [`HuffDecoder`][crate::comp::HuffDecoder]
+    /// trait.
+    pub fn decode_next<I: Iterator<Item = bool>>(
+        &self,
+        cursor: &mut crate::comp::BitCursor<I>,
+    ) -> Option<(L, u8)> {
+        let mut index = self.root()?;
+        let mut len = 0u8;
+
+        while let Some([left, right]) = self.nodes[index].children() {
+            let bit = cursor.peek(1) == 1;
+            cursor.advance(1);
+            len += 1;
+            index = if bit { right } else { left };
+        }
+
+        Some((self.nodes[index].leaf().letter()?.clone(), len))
+    }
+}
+
+/// Error returned by [`HuffTree::try_from_bin`] when its input doesn't
+/// describe a well-formed tree.
+#[derive(Debug)]
+pub struct TryFromBinError(String);
+
+impl std::fmt::Display for TryFromBinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "malformed HuffTree binary representation: {}", self.0)
+    }
+}
+
+impl std::error::Error for TryFromBinError {}
+
+impl<L: HuffLetterAsBytes> HuffTree<L> {
+    /// Returns the tree represented in binary, to be stored as a header for
+    /// an encoded file:
+    ///
+    /// * `0` marks a leaf, immediately followed by its letter's
+    ///   [`L::BYTE_LEN`][HuffLetterAsBytes::BYTE_LEN] bytes.
+    /// * `1` marks a joint node, followed by its two children.
+    ///
+    /// Does not store frequencies — it's only meant to reconstruct a
+    /// same-shaped tree for decoding.
+    ///
+    /// # Example
+    /// ```
+    /// use huff_coding::prelude::*;
+    ///
+    /// let tree = HuffTree::from_weights(ByteWeights::from_bytes(b"abbccc"));
+    /// let bin = tree.as_bin();
+    ///
+    /// assert_eq!(HuffTree::<u8>::try_from_bin(bin).unwrap().read_codes(), tree.read_codes());
+    /// ```
+    pub fn as_bin(&self) -> BitVec<Msb0, u8> {
+        let mut bin = BitVec::new();
+        if let Some(root) = self.root() {
+            self.write_bin(root, &mut bin);
+        }
+
+        bin
+    }
+
+    fn write_bin(&self, index: usize, bin: &mut BitVec<Msb0, u8>) {
+        let branch = &self.nodes[index];
+        match branch.children() {
+            Some([left, right]) => {
+                bin.push(true);
+                self.write_bin(left, bin);
+                self.write_bin(right, bin);
+            }
+            None => {
+                bin.push(false);
+                for byte in branch.leaf().letter().unwrap().as_bytes() {
+                    utils::push_byte(bin, byte);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds a `HuffTree` with the same shape (and thus the same codes)
+    /// from its [`as_bin`][Self::as_bin] output.
+    pub fn try_from_bin(bin: BitVec<Msb0, u8>) -> Result<Self, TryFromBinError> {
+        let mut nodes = Vec::new();
+        let mut bits = bin.into_iter();
+
+        let root = Self::read_bin(&mut bits, &mut nodes)?;
+
+        let mut tree = HuffTree {
+            nodes,
+            root: Some(root),
+        };
+        tree.set_codes();
+
+        Ok(tree)
+    }
+
+    fn read_bin(
+        bits: &mut impl Iterator<Item = bool>,
+        nodes: &mut Vec<HuffBranch<L>>,
+    ) -> Result<usize, TryFromBinError> {
+        let is_joint = bits
+            .next()
+            .ok_or_else(|| TryFromBinError("unexpected end of input".into()))?;
+
+        if is_joint {
+            let left = Self::read_bin(bits, nodes)?;
+            let right = Self::read_bin(bits, nodes)?;
+
+            let frequency = nodes[left].leaf().frequency() + nodes[right].leaf().frequency();
+            let index = nodes.len();
+
+            let mut branch = HuffBranch::new(HuffLeaf::new(None, frequency));
+            branch.set_children(left, right);
+            nodes.push(branch);
+
+            nodes[left].set_pos_in_parent(0);
+            nodes[right].set_pos_in_parent(1);
+            nodes[left].set_parent(index);
+            nodes[right].set_parent(index);
+
+            Ok(index)
+        } else {
+            let mut bytes = Vec::with_capacity(L::BYTE_LEN);
+            for _ in 0..L::BYTE_LEN {
+                bytes.push(
+                    utils::read_byte(bits)
+                        .ok_or_else(|| TryFromBinError("unexpected end of input".into()))?,
+                );
+            }
+
+            let index = nodes.len();
+            nodes.push(HuffBranch::new(HuffLeaf::new(Some(L::from_bytes(&bytes)), 1)));
+
+            Ok(index)
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Small bit-level helpers shared by [`crate::tree`] and [`crate::comp`].
+
+use bitvec::prelude::*;
+
+/// Pushes `byte`'s 8 bits, most-significant first, onto `bin`.
+pub(crate) fn push_byte(bin: &mut BitVec<Msb0, u8>, byte: u8) {
+    for i in (0..8).rev() {
+        bin.push((byte >> i) & 1 == 1);
+    }
+}
+
+/// Reads 8 bits, most-significant first, off of `bits` and assembles them
+/// into a byte. Returns `None` if `bits` runs out early.
+pub(crate) fn read_byte(bits: &mut impl Iterator<Item = bool>) -> Option<u8> {
+    let mut byte = 0u8;
+    for _ in 0..8 {
+        byte = (byte << 1) | bits.next()? as u8;
+    }
+
+    Some(byte)
+}
+
+/// Pushes `value`'s 4 bytes, big-endian, onto `bin`.
+pub(crate) fn push_u32(bin: &mut BitVec<Msb0, u8>, value: u32) {
+    for byte in value.to_be_bytes() {
+        push_byte(bin, byte);
+    }
+}
+
+/// Reads a big-endian `u32` off of `bits`. Returns `None` if `bits` runs out early.
+pub(crate) fn read_u32(bits: &mut impl Iterator<Item = bool>) -> Option<u32> {
+    let mut bytes = [0u8; 4];
+    for byte in &mut bytes {
+        *byte = read_byte(bits)?;
+    }
+
+    Some(u32::from_be_bytes(bytes))
+}
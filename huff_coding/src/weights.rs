@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::tree::letter::HuffLetter;
+
+/// A source of per-letter weights (frequencies) that a
+/// [`HuffTree`][crate::tree::HuffTree] can be built from.
+pub trait Weights<L: HuffLetter> {
+    /// Returns every letter along with its weight.
+    fn letters_with_weights(&self) -> HashMap<L, usize>;
+}
+
+impl<L: HuffLetter> Weights<L> for HashMap<L, usize> {
+    fn letters_with_weights(&self) -> HashMap<L, usize> {
+        self.clone()
+    }
+}
+
+/// Builds a `HashMap` of letters mapped to the number of times they occur in
+/// `letters`, suitable for use as a [`Weights`] source.
+///
+/// # Example
+/// ```
+/// use huff_coding::weights::build_weights_map;
+///
+/// let weights = build_weights_map(&['a', 'a', 'b']);
+/// assert_eq!(weights.get(&'a'), Some(&2));
+/// assert_eq!(weights.get(&'b'), Some(&1));
+/// ```
+pub fn build_weights_map<L: HuffLetter>(letters: &[L]) -> HashMap<L, usize> {
+    let mut map = HashMap::new();
+    for letter in letters {
+        *map.entry(letter.clone()).or_insert(0) += 1;
+    }
+
+    map
+}
+
+/// [`Weights`] source over `u8`, precomputed once via [`ByteWeights::from_bytes`]
+/// instead of going through a generic `HashMap`.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteWeights {
+    counts: [usize; 256],
+}
+
+impl ByteWeights {
+    /// Counts the occurrences of every byte in `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut counts = [0usize; 256];
+        for &byte in bytes {
+            counts[byte as usize] += 1;
+        }
+
+        ByteWeights { counts }
+    }
+}
+
+impl Weights<u8> for ByteWeights {
+    fn letters_with_weights(&self) -> HashMap<u8, usize> {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(byte, &count)| (byte as u8, count))
+            .collect()
+    }
+}